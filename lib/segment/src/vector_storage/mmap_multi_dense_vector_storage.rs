@@ -0,0 +1,395 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::types::PointOffsetType;
+use memory::madvise::Advice;
+use memory::mmap_ops;
+use memory::mmap_type::{MmapBitSlice, MmapSlice};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::{MultiDenseVector, MultiVector};
+use crate::types::Distance;
+use crate::vector_storage::multi_vector_storage::MultiVectorStorage;
+use crate::vector_storage::VectorStorageEnum;
+
+const OFFSETS_FILE: &str = "multivector_offsets.dat";
+const DATA_FILE: &str = "multivector_vectors.dat";
+const DELETED_FILE: &str = "multivector_deleted.dat";
+
+/// Growth factor applied to the data file whenever it runs out of room for a new record.
+const DATA_FILE_GROWTH_FACTOR: usize = 2;
+
+/// Describes where a single point's sub-vectors live inside the flat data file.
+///
+/// `num_vectors == 0` marks a point that was never written (e.g. a gap left by
+/// out-of-order inserts), which `get_multi` treats as an empty multivector.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+struct MultiVectorOffset {
+    start: u64,
+    num_vectors: u32,
+    _padding: u32,
+}
+
+/// Memory-mapped counterpart to [`SimpleMultiDenseVectorStorage`](super::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage).
+///
+/// Sub-vectors are kept out of the heap entirely: a fixed-stride `offsets` mmap maps a
+/// `point_offset` to a `(start, num_vectors)` pair, where `start` is the index of the first
+/// `f32` in `data`, and the variable-length payload itself lives in a contiguous `data`
+/// mmap of dim-sized `f32` chunks. `get_multi` never copies; it slices `data` directly
+/// using the offsets entry. This trades keeping every vector resident on the heap for a
+/// page fault, which is the same trade the plain dense mmap storage makes.
+pub struct MmapMultiDenseVectorStorage {
+    dim: usize,
+    distance: Distance,
+    base_path: PathBuf,
+    offsets: MmapSlice<MultiVectorOffset>,
+    data: MmapSlice<f32>,
+    /// Number of `f32` slots currently occupied in `data`.
+    data_len: usize,
+    deleted: MmapBitSlice,
+    deleted_count: usize,
+}
+
+impl MmapMultiDenseVectorStorage {
+    fn offsets_path(path: &Path) -> PathBuf {
+        path.join(OFFSETS_FILE)
+    }
+
+    fn data_path(path: &Path) -> PathBuf {
+        path.join(DATA_FILE)
+    }
+
+    fn deleted_path(path: &Path) -> PathBuf {
+        path.join(DELETED_FILE)
+    }
+
+    fn offset_entry(&self, key: PointOffsetType) -> Option<MultiVectorOffset> {
+        self.offsets.get(key as usize).copied()
+    }
+
+    /// Ensure the data file has room for `additional` more `f32` values, growing (and
+    /// remapping) it if necessary.
+    fn reserve_data(&mut self, additional: usize) -> OperationResult<()> {
+        let required = self.data_len + additional;
+        if required <= self.data.len() {
+            return Ok(());
+        }
+        let mut new_len = self.data.len().max(self.dim).max(1);
+        while new_len < required {
+            new_len *= DATA_FILE_GROWTH_FACTOR;
+        }
+        let path = Self::data_path(&self.base_path);
+        mmap_ops::create_and_ensure_length(&path, new_len * std::mem::size_of::<f32>())?;
+        self.data = unsafe {
+            MmapSlice::try_from(mmap_ops::open_write_mmap(
+                &path,
+                Advice::Normal,
+                false,
+            )?)?
+        };
+        Ok(())
+    }
+
+    /// Ensure the offsets table can address `key`, growing (and remapping) it if necessary.
+    fn reserve_offsets(&mut self, key: PointOffsetType) -> OperationResult<()> {
+        let required = key as usize + 1;
+        if required <= self.offsets.len() {
+            return Ok(());
+        }
+        let mut new_len = self.offsets.len().max(1);
+        while new_len < required {
+            new_len *= DATA_FILE_GROWTH_FACTOR;
+        }
+        let path = Self::offsets_path(&self.base_path);
+        mmap_ops::create_and_ensure_length(
+            &path,
+            new_len * std::mem::size_of::<MultiVectorOffset>(),
+        )?;
+        self.offsets = unsafe {
+            MmapSlice::try_from(mmap_ops::open_write_mmap(
+                &path,
+                Advice::Normal,
+                false,
+            )?)?
+        };
+        Ok(())
+    }
+
+    /// Ensure the deleted bitslice can address `key`, growing (and remapping) it if
+    /// necessary, the same way [`Self::reserve_offsets`] grows the offsets table.
+    fn reserve_deleted(&mut self, key: PointOffsetType) -> OperationResult<()> {
+        let required_bits = key as usize + 1;
+        if required_bits <= self.deleted.len() {
+            return Ok(());
+        }
+        let mut new_len_bits = self.deleted.len().max(8);
+        while new_len_bits < required_bits {
+            new_len_bits *= DATA_FILE_GROWTH_FACTOR;
+        }
+        let path = Self::deleted_path(&self.base_path);
+        mmap_ops::create_and_ensure_length(&path, new_len_bits.div_ceil(8))?;
+        let deleted_mmap = mmap_ops::open_write_mmap(&path, Advice::Normal, false)?;
+        self.deleted = MmapBitSlice::try_from(deleted_mmap, 0)?;
+        Ok(())
+    }
+
+    /// Read back the sub-vectors stored for `key` as a borrowed [`MultiVector`] view.
+    pub fn get_multi(&self, key: PointOffsetType) -> MultiVector {
+        match self.offset_entry(key) {
+            Some(offset) if offset.num_vectors > 0 => {
+                let start = offset.start as usize;
+                let len = offset.num_vectors as usize * self.dim;
+                MultiVector::new(self.dim, &self.data[start..start + len])
+            }
+            _ => MultiVector::new(self.dim, &[]),
+        }
+    }
+
+    /// Write (or overwrite) the sub-vectors for `key`. Overwriting always appends a fresh
+    /// copy rather than mutating in place, since a point's vector count can change between
+    /// writes; the old bytes are simply abandoned in the data file.
+    pub fn insert_multi(
+        &mut self,
+        key: PointOffsetType,
+        vector: &MultiDenseVector,
+    ) -> OperationResult<()> {
+        let flattened = vector.flattened_vectors();
+        self.reserve_offsets(key)?;
+        self.reserve_data(flattened.len())?;
+
+        let start = self.data_len;
+        self.data[start..start + flattened.len()].copy_from_slice(flattened);
+        self.data_len += flattened.len();
+
+        self.offsets[key as usize] = MultiVectorOffset {
+            start: start as u64,
+            num_vectors: vector.multi_vectors().count() as u32,
+            _padding: 0,
+        };
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_deleted(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).as_deref().copied().unwrap_or(false)
+    }
+
+    pub fn set_deleted(
+        &mut self,
+        key: PointOffsetType,
+        deleted: bool,
+    ) -> OperationResult<bool> {
+        if deleted {
+            self.reserve_deleted(key)?;
+        } else if key as usize >= self.deleted.len() {
+            return Ok(false);
+        }
+        let was_deleted = self.deleted.replace(key as usize, deleted);
+        if was_deleted != deleted {
+            if deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count = self.deleted_count.saturating_sub(1);
+            }
+        }
+        Ok(was_deleted)
+    }
+
+    pub fn deleted_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    /// Flush all three mmap regions to disk.
+    pub fn flush(&self) -> OperationResult<()> {
+        self.offsets
+            .flusher()
+            .flush()
+            .map_err(|err| OperationError::service_error(&format!("failed to flush offsets: {err}")))?;
+        self.data
+            .flusher()
+            .flush()
+            .map_err(|err| OperationError::service_error(&format!("failed to flush vector data: {err}")))?;
+        self.deleted
+            .flusher()
+            .flush()
+            .map_err(|err| OperationError::service_error(&format!("failed to flush deleted flags: {err}")))?;
+        Ok(())
+    }
+
+    /// Advise the OS to keep the mmap'd regions resident, pulling them into the page cache
+    /// ahead of the first query.
+    pub fn populate(&self) -> OperationResult<()> {
+        self.offsets.populate();
+        self.data.populate();
+        self.deleted.populate();
+        Ok(())
+    }
+
+    /// Advise the OS that the mmap'd regions are not needed soon, letting it evict them
+    /// from the page cache under memory pressure.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        mmap_ops::clear_disk_cache(&Self::offsets_path(&self.base_path))?;
+        mmap_ops::clear_disk_cache(&Self::data_path(&self.base_path))?;
+        mmap_ops::clear_disk_cache(&Self::deleted_path(&self.base_path))?;
+        Ok(())
+    }
+}
+
+/// Open (creating empty backing files on first use) an [`MmapMultiDenseVectorStorage`] at
+/// `path`, mirroring [`open_simple_multi_dense_vector_storage`](super::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage).
+pub fn open_mmap_multi_dense_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    std::fs::create_dir_all(path)?;
+
+    let offsets_path = MmapMultiDenseVectorStorage::offsets_path(path);
+    if !offsets_path.exists() {
+        mmap_ops::create_and_ensure_length(&offsets_path, std::mem::size_of::<MultiVectorOffset>())?;
+    }
+    let offsets = unsafe {
+        MmapSlice::try_from(mmap_ops::open_write_mmap(&offsets_path, Advice::Normal, false)?)?
+    };
+
+    let data_path = MmapMultiDenseVectorStorage::data_path(path);
+    if !data_path.exists() {
+        mmap_ops::create_and_ensure_length(&data_path, dim * std::mem::size_of::<f32>())?;
+    }
+    let data = unsafe {
+        MmapSlice::try_from(mmap_ops::open_write_mmap(&data_path, Advice::Normal, false)?)?
+    };
+
+    let deleted_path = MmapMultiDenseVectorStorage::deleted_path(path);
+    if !deleted_path.exists() {
+        mmap_ops::create_and_ensure_length(&deleted_path, 1)?;
+    }
+    let deleted_mmap = mmap_ops::open_write_mmap(&deleted_path, Advice::Normal, false)?;
+    let deleted = MmapBitSlice::try_from(deleted_mmap, 0)?;
+    let deleted_count = deleted.count_ones();
+
+    // `data_len` is recovered from the highest populated offset entry rather than stored
+    // separately, since it is fully determined by the offsets table on a clean reopen.
+    let data_len = offsets
+        .iter()
+        .filter(|entry| entry.num_vectors > 0)
+        .map(|entry| entry.start as usize + entry.num_vectors as usize * dim)
+        .max()
+        .unwrap_or(0);
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::MultiDenseMmap(MmapMultiDenseVectorStorage {
+            dim,
+            distance,
+            base_path: path.to_path_buf(),
+            offsets,
+            data,
+            data_len,
+            deleted,
+            deleted_count,
+        }),
+    )))
+}
+
+impl MultiVectorStorage for MmapMultiDenseVectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> MultiVector {
+        MmapMultiDenseVectorStorage::get_multi(self, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_deleted_grows_past_the_initial_one_byte_file() {
+        let dir = tempfile::Builder::new()
+            .prefix("mmap-multi-dense")
+            .tempdir()
+            .unwrap();
+        let storage =
+            open_mmap_multi_dense_vector_storage(dir.path(), 4, Distance::Cosine).unwrap();
+        let mut guard = storage.borrow_mut();
+        let VectorStorageEnum::MultiDenseMmap(storage) = &mut *guard else {
+            panic!("expected mmap multivector storage");
+        };
+
+        // The deleted file starts at a single byte (8 bits); this id is well past that,
+        // so `set_deleted` must grow the backing file rather than silently no-op.
+        let key = 5_000;
+        let was_deleted = storage.set_deleted(key, true).unwrap();
+        assert!(!was_deleted);
+        assert!(storage.is_deleted(key));
+        assert_eq!(storage.deleted_count(), 1);
+
+        let was_deleted = storage.set_deleted(key, false).unwrap();
+        assert!(was_deleted);
+        assert!(!storage.is_deleted(key));
+        assert_eq!(storage.deleted_count(), 0);
+    }
+
+    #[test]
+    fn insert_multi_and_get_multi_round_trip() {
+        let dir = tempfile::Builder::new()
+            .prefix("mmap-multi-dense")
+            .tempdir()
+            .unwrap();
+        let storage =
+            open_mmap_multi_dense_vector_storage(dir.path(), 4, Distance::Cosine).unwrap();
+        let mut guard = storage.borrow_mut();
+        let VectorStorageEnum::MultiDenseMmap(storage) = &mut *guard else {
+            panic!("expected mmap multivector storage");
+        };
+
+        let first = MultiDenseVector::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], 4);
+        let second = MultiDenseVector::new(vec![9.0, 10.0, 11.0, 12.0], 4);
+        storage.insert_multi(0, &first).unwrap();
+        storage.insert_multi(1, &second).unwrap();
+
+        assert_eq!(
+            storage.get_multi(0).flattened_vectors(),
+            first.flattened_vectors()
+        );
+        assert_eq!(
+            storage.get_multi(1).flattened_vectors(),
+            second.flattened_vectors()
+        );
+    }
+
+    #[test]
+    fn flush_and_reopen_round_trip() {
+        let dir = tempfile::Builder::new()
+            .prefix("mmap-multi-dense")
+            .tempdir()
+            .unwrap();
+        let vector = MultiDenseVector::new(vec![1.0, 2.0, 3.0, 4.0], 4);
+
+        {
+            let storage =
+                open_mmap_multi_dense_vector_storage(dir.path(), 4, Distance::Cosine).unwrap();
+            let mut guard = storage.borrow_mut();
+            let VectorStorageEnum::MultiDenseMmap(storage) = &mut *guard else {
+                panic!("expected mmap multivector storage");
+            };
+            storage.insert_multi(0, &vector).unwrap();
+            storage.set_deleted(1, true).unwrap();
+            storage.flush().unwrap();
+        }
+
+        let storage =
+            open_mmap_multi_dense_vector_storage(dir.path(), 4, Distance::Cosine).unwrap();
+        let mut guard = storage.borrow_mut();
+        let VectorStorageEnum::MultiDenseMmap(storage) = &mut *guard else {
+            panic!("expected mmap multivector storage");
+        };
+        assert_eq!(
+            storage.get_multi(0).flattened_vectors(),
+            vector.flattened_vectors()
+        );
+        assert!(storage.is_deleted(1));
+        assert_eq!(storage.deleted_count(), 1);
+    }
+}
@@ -0,0 +1,17 @@
+use common::types::PointOffsetType;
+
+use crate::data_types::vectors::MultiVector;
+
+/// Narrow read contract shared by every multivector-backed [`VectorStorage`](super::VectorStorage)
+/// implementation, letting [`CustomQueryScorer`](super::query_scorer::multi_custom_query_scorer::CustomQueryScorer)
+/// score against [`SimpleMultiDenseVectorStorage`](super::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage)
+/// and [`MmapMultiDenseVectorStorage`](super::mmap_multi_dense_vector_storage::MmapMultiDenseVectorStorage)
+/// interchangeably.
+pub trait MultiVectorStorage {
+    /// Read back the sub-vectors stored for `key` as a borrowed [`MultiVector`] view.
+    fn get_multi(&self, key: PointOffsetType) -> MultiVector;
+
+    /// Record that a scoring pass consumed a `get_multi` result. No-op by default, since
+    /// not every backend tracks operational counters.
+    fn record_score_call(&self) {}
+}
@@ -6,8 +6,8 @@ use super::score_multivector;
 use crate::data_types::vectors::MultiVector;
 use crate::spaces::metric::Metric;
 use crate::vector_storage::query::{Query, TransformInto};
+use crate::vector_storage::multi_vector_storage::MultiVectorStorage;
 use crate::vector_storage::query_scorer::QueryScorer;
-use crate::vector_storage::MultiVectorStorage;
 
 pub struct CustomQueryScorer<
     'a,
@@ -47,7 +47,8 @@ impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage, TQuery: Query<Mult
     #[inline]
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
         let stored = self.vector_storage.get_multi(idx);
-        self.score(stored)
+        self.vector_storage.record_score_call();
+        self.score(&stored)
     }
 
     #[inline]
@@ -56,7 +57,10 @@ impl<'a, TMetric: Metric, TVectorStorage: MultiVectorStorage, TQuery: Query<Mult
             .score_by(|example| score_multivector::<TMetric>(example, against))
     }
 
-    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
-        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let vector_a = self.vector_storage.get_multi(point_a);
+        let vector_b = self.vector_storage.get_multi(point_b);
+        self.vector_storage.record_score_call();
+        score_multivector::<TMetric>(&vector_a, &vector_b)
     }
 }
@@ -1,22 +1,126 @@
-use std::sync::atomic::AtomicBool;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
-use bitvec::prelude::BitVec;
+use bitvec::prelude::{BitSlice, BitVec};
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
-use crate::data_types::vectors::MultiDenseVector;
+use crate::data_types::vectors::{MultiDenseVector, MultiVector, VectorRef};
 use crate::types::Distance;
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::common::StoredRecord;
-use crate::vector_storage::VectorStorageEnum;
+use crate::vector_storage::multi_vector_storage::MultiVectorStorage;
+use crate::vector_storage::spill_buffer::SpillBuffer;
+use crate::vector_storage::{VectorStorage, VectorStorageDatatype, VectorStorageEnum};
+
+/// Resident ring size for the [`SpillBuffer`] used while loading. Keeps the working set
+/// during load far below "whole collection", independent of how large the column is.
+const LOAD_SPILL_RING_CAPACITY: usize = 4096;
 
 type StoredMultiDenseVector = StoredRecord<MultiDenseVector>;
 
+/// RocksDB key holding the per-column compression flag. Not a valid bincode-encoded
+/// `PointOffsetType`, so it can share the column with point records without colliding.
+const COMPRESSION_METADATA_KEY: &[u8] = b"__multivector_compression__";
+
+/// Codec tag prefixed to every stored record so a column can be read back correctly even
+/// mid-migration, when old (raw) and new (lz4) records are mixed.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RecordCodec {
+    Raw = 0,
+    Lz4 = 1,
+}
+
+impl RecordCodec {
+    fn from_tag(tag: u8) -> OperationResult<Self> {
+        match tag {
+            0 => Ok(RecordCodec::Raw),
+            1 => Ok(RecordCodec::Lz4),
+            other => Err(OperationError::service_error(&format!(
+                "unknown multivector record codec tag: {other}"
+            ))),
+        }
+    }
+}
+
+fn encode_record(record: &StoredMultiDenseVector, compressed: bool) -> OperationResult<Vec<u8>> {
+    let raw = bincode::serialize(record)
+        .map_err(|_| OperationError::service_error("cannot serialize multivector record"))?;
+    if compressed {
+        let mut out = Vec::with_capacity(raw.len() + 1);
+        out.push(RecordCodec::Lz4 as u8);
+        out.extend_from_slice(&lz4_flex::compress_prepend_size(&raw));
+        Ok(out)
+    } else {
+        let mut out = Vec::with_capacity(raw.len() + 1);
+        out.push(RecordCodec::Raw as u8);
+        out.extend_from_slice(&raw);
+        Ok(out)
+    }
+}
+
+/// Operational counters for a [`SimpleMultiDenseVectorStorage`], kept as atomics so they
+/// can be bumped from `&self` methods (`get_multi`, scoring) without a lock.
+#[derive(Default)]
+struct MultiDenseVectorStorageCounters {
+    get_multi_calls: AtomicU64,
+    score_calls: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+/// Point-in-time snapshot of a [`SimpleMultiDenseVectorStorage`]'s instrumentation. Cheap
+/// to build and hand to the surrounding segment layer for scraping, so users can see
+/// storage-level amplification and deletion pressure per segment rather than only coarse
+/// collection-wide stats.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MultiDenseVectorStorageMetrics {
+    pub get_multi_calls: u64,
+    pub score_calls: u64,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    /// Wall-clock time spent iterating RocksDB while the storage was opened.
+    pub open_iteration_micros: u64,
+    pub live_count: usize,
+    pub deleted_count: usize,
+    /// Total size, in `f32` elements, of every sub-vector currently stored.
+    pub total_sparse_size: usize,
+}
+
+/// Suffix appended to `database_column_name` to get the name of the dedicated deletion
+/// subspace, following the convention of giving each value type its own keyspace rather
+/// than burying it inside the record column.
+const DELETED_COLUMN_SUFFIX: &str = "-deleted";
+
+fn deleted_column_name(database_column_name: &str) -> String {
+    format!("{database_column_name}{DELETED_COLUMN_SUFFIX}")
+}
+
+fn deleted_key(point_id: PointOffsetType) -> Vec<u8> {
+    bincode::serialize(&point_id).unwrap()
+}
+
+fn decode_record(bytes: &[u8]) -> OperationResult<StoredMultiDenseVector> {
+    let (tag, payload) = bytes
+        .split_first()
+        .ok_or_else(|| OperationError::service_error("empty multivector record"))?;
+    let raw = match RecordCodec::from_tag(*tag)? {
+        RecordCodec::Raw => payload.to_vec(),
+        RecordCodec::Lz4 => lz4_flex::decompress_size_prepended(payload)
+            .map_err(|err| OperationError::service_error(&format!("lz4 decompress failed: {err}")))?,
+    };
+    bincode::deserialize(&raw)
+        .map_err(|_| OperationError::service_error("cannot deserialize record from db"))
+}
+
 /// In-memory vector storage with on-update persistence using `store`
 #[allow(unused)]
 pub struct SimpleMultiDenseVectorStorage {
@@ -25,11 +129,24 @@ pub struct SimpleMultiDenseVectorStorage {
     /// Keep vectors in memory
     vectors: Vec<MultiDenseVector>,
     db_wrapper: DatabaseColumnWrapper,
+    /// Dedicated column holding only `point_offset -> deleted` tombstones, so reopening
+    /// doesn't need to deserialize every full vector record just to rebuild `deleted`.
+    deleted_db_wrapper: DatabaseColumnWrapper,
     update_buffer: StoredMultiDenseVector,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
     /// Current number of deleted vectors.
     deleted_count: usize,
+    /// Whether new records are lz4-compressed before being written to `db_wrapper`.
+    /// Existing uncompressed columns keep working regardless, since every record also
+    /// carries its own codec tag.
+    compressed: bool,
+    /// Total size, in `f32` elements, of every sub-vector currently stored. Updated
+    /// incrementally as records are loaded and written.
+    total_sparse_size: usize,
+    /// Time spent iterating RocksDB while this storage was opened.
+    open_iteration_micros: u64,
+    counters: MultiDenseVectorStorageCounters,
 }
 
 #[allow(unused)]
@@ -40,28 +157,86 @@ pub fn open_simple_multi_dense_vector_storage(
     distance: Distance,
     stopped: &AtomicBool,
 ) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
-    let mut vectors = vec![];
+    open_simple_multi_dense_vector_storage_with_compression(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        false,
+        stopped,
+    )
+}
+
+/// Same as [`open_simple_multi_dense_vector_storage`], but lets the caller opt new writes
+/// into lz4 compression. The flag is persisted under [`COMPRESSION_METADATA_KEY`] so
+/// reopening the column picks the same behaviour back up without the caller tracking it.
+#[allow(unused)]
+pub fn open_simple_multi_dense_vector_storage_with_compression(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    compressed: bool,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
-    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    let db_wrapper = DatabaseColumnWrapper::new(database.clone(), database_column_name);
+    let deleted_db_wrapper =
+        DatabaseColumnWrapper::new(database, &deleted_column_name(database_column_name));
+
+    // Reconstruct the deleted bitvec from the compact deletion subspace alone, without
+    // touching (let alone deserializing) a single record in the vector column.
+    for (key, _value) in deleted_db_wrapper.lock_db().iter()? {
+        let point_id: PointOffsetType = bincode::deserialize(&key).map_err(|_| {
+            OperationError::service_error("cannot deserialize point id from deleted subspace")
+        })?;
+        bitvec_set_deleted(&mut deleted, point_id, true);
+        deleted_count += 1;
+    }
+
+    let stored_compressed = db_wrapper
+        .lock_db()
+        .get(COMPRESSION_METADATA_KEY)?
+        .is_some_and(|bytes| bytes == [1]);
+    let compressed = compressed || stored_compressed;
+    if compressed && !stored_compressed {
+        db_wrapper.put(COMPRESSION_METADATA_KEY.to_vec(), vec![1u8])?;
+    }
 
-    let mut total_vector_count = 0;
     let mut total_sparse_size = 0;
-    db_wrapper.lock_db().iter()?;
+    let mut staging = SpillBuffer::with_capacity(LOAD_SPILL_RING_CAPACITY)?;
+    let iteration_started_at = Instant::now();
     for (key, value) in db_wrapper.lock_db().iter()? {
+        if &key[..] == COMPRESSION_METADATA_KEY {
+            continue;
+        }
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
-        let stored_record: StoredMultiDenseVector = bincode::deserialize(&value)
-            .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
+        let stored_record = decode_record(&value)?;
 
-        // Propagate deleted flag
-        if stored_record.deleted {
+        // Deletion state is authoritative in `deleted_db_wrapper` (reconstructed above),
+        // so `stored_record.deleted` is only ever consulted here for columns written
+        // before the deletion subspace existed.
+        let already_marked_deleted = deleted.get(point_id as usize).map(|flag| *flag).unwrap_or(false);
+        if stored_record.deleted && !already_marked_deleted {
             bitvec_set_deleted(&mut deleted, point_id, true);
             deleted_count += 1;
         }
-        vectors.insert(point_id as usize, stored_record.vector);
+        total_sparse_size += stored_record.vector.flattened_vectors().len();
+        staging.insert(point_id, stored_record.vector)?;
 
         check_process_stopped(stopped)?;
     }
+    let open_iteration_micros = iteration_started_at.elapsed().as_micros() as u64;
+
+    // Drain in ascending point_id order so each record still lands at its correct offset.
+    // `drain_in_order` streams the merge itself, so this loop is the only place still
+    // holding the whole column resident, same as the baseline's `vectors` Vec did.
+    let mut vectors = vec![];
+    for entry in staging.drain_in_order()? {
+        let (point_id, vector) = entry?;
+        vectors.insert(point_id as usize, vector);
+    }
 
     Ok(Arc::new(AtomicRefCell::new(
         VectorStorageEnum::MultiDenseSimple(SimpleMultiDenseVectorStorage {
@@ -69,23 +244,29 @@ pub fn open_simple_multi_dense_vector_storage(
             distance,
             vectors,
             db_wrapper,
+            deleted_db_wrapper,
             update_buffer: StoredMultiDenseVector {
                 deleted: false,
                 vector: MultiDenseVector::default(),
             },
             deleted,
             deleted_count,
+            compressed,
+            total_sparse_size,
+            open_iteration_micros,
+            counters: MultiDenseVectorStorageCounters::default(),
         }),
     )))
 }
 
 impl SimpleMultiDenseVectorStorage {
-    /// Set deleted flag for given key. Returns previous deleted state.
+    /// Set deleted flag for given key. Returns previous deleted state. Persists the flag
+    /// into the dedicated deletion subspace rather than relying on the vector record.
     #[inline]
     #[allow(unused)]
-    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> OperationResult<bool> {
         if key as usize >= self.vectors.len() {
-            return false;
+            return Ok(false);
         }
         let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
         if was_deleted != deleted {
@@ -95,7 +276,39 @@ impl SimpleMultiDenseVectorStorage {
                 self.deleted_count = self.deleted_count.saturating_sub(1);
             }
         }
-        was_deleted
+        if deleted {
+            self.deleted_db_wrapper.put(deleted_key(key), vec![1u8])?;
+        } else {
+            self.deleted_db_wrapper.delete(deleted_key(key))?;
+        }
+        Ok(was_deleted)
+    }
+
+    /// Tombstone every point in `[from, to)` in one batched write to the deletion subspace,
+    /// useful when dropping a rebuilt segment's worth of ids at once instead of calling
+    /// [`Self::set_deleted`] one point at a time. Ids at or beyond `self.vectors.len()`
+    /// were never valid points and are skipped rather than tombstoned.
+    #[allow(unused)]
+    pub fn delete_range(
+        &mut self,
+        from: PointOffsetType,
+        to: PointOffsetType,
+    ) -> OperationResult<()> {
+        let to = to.min(self.vectors.len() as PointOffsetType);
+        if to <= from {
+            return Ok(());
+        }
+
+        let mut entries = Vec::with_capacity((to - from) as usize);
+        for point_id in from..to {
+            let was_deleted = bitvec_set_deleted(&mut self.deleted, point_id, true);
+            if !was_deleted {
+                self.deleted_count += 1;
+            }
+            entries.push((deleted_key(point_id), vec![1u8]));
+        }
+        self.deleted_db_wrapper.put_batch(entries)?;
+        Ok(())
     }
 
     #[allow(unused)]
@@ -113,14 +326,305 @@ impl SimpleMultiDenseVectorStorage {
         }
 
         // Store updated record
-        self.db_wrapper.put(
-            bincode::serialize(&key).unwrap(),
-            bincode::serialize(&record).unwrap(),
-        )?;
+        let bytes = encode_record(record, self.compressed)?;
+        self.counters
+            .bytes_written
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        self.db_wrapper
+            .put(bincode::serialize(&key).unwrap(), bytes)?;
+
+        Ok(())
+    }
+
+    /// Read back the sub-vectors stored for `key` as a borrowed [`MultiVector`] view,
+    /// counting the call towards [`MultiDenseVectorStorageMetrics::get_multi_calls`].
+    ///
+    /// Returns the same borrowed-view type as
+    /// [`MmapMultiDenseVectorStorage::get_multi`](super::mmap_multi_dense_vector_storage::MmapMultiDenseVectorStorage::get_multi),
+    /// so the two backends are interchangeable behind [`MultiVectorStorage`](super::multi_vector_storage::MultiVectorStorage).
+    #[allow(unused)]
+    pub fn get_multi(&self, key: PointOffsetType) -> MultiVector {
+        self.counters
+            .get_multi_calls
+            .fetch_add(1, Ordering::Relaxed);
+        let vector = &self.vectors[key as usize];
+        self.counters.bytes_read.fetch_add(
+            (vector.flattened_vectors().len() * std::mem::size_of::<f32>()) as u64,
+            Ordering::Relaxed,
+        );
+        MultiVector::new(self.dim, vector.flattened_vectors())
+    }
+
+    /// Record that a scoring pass consumed a `get_multi` result, towards
+    /// [`MultiDenseVectorStorageMetrics::score_calls`].
+    #[allow(unused)]
+    pub fn record_score_call(&self) {
+        self.counters.score_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot the storage's operational counters for the surrounding segment layer to
+    /// scrape.
+    #[allow(unused)]
+    pub fn metrics(&self) -> MultiDenseVectorStorageMetrics {
+        MultiDenseVectorStorageMetrics {
+            get_multi_calls: self.counters.get_multi_calls.load(Ordering::Relaxed),
+            score_calls: self.counters.score_calls.load(Ordering::Relaxed),
+            bytes_read: self.counters.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.counters.bytes_written.load(Ordering::Relaxed),
+            open_iteration_micros: self.open_iteration_micros,
+            live_count: self.vectors.len().saturating_sub(self.deleted_count),
+            deleted_count: self.deleted_count,
+            total_sparse_size: self.total_sparse_size,
+        }
+    }
+}
+
+impl MultiVectorStorage for SimpleMultiDenseVectorStorage {
+    fn get_multi(&self, key: PointOffsetType) -> MultiVector {
+        SimpleMultiDenseVectorStorage::get_multi(self, key)
+    }
+
+    fn record_score_call(&self) {
+        SimpleMultiDenseVectorStorage::record_score_call(self)
+    }
+}
+
+// Relies on `VectorRef`/`MultiDenseVector` conversions from `data_types::vectors` to let
+// this storage participate in segment build/merge like any other `VectorStorage`; those
+// conversions live outside this module and aren't part of this change.
+impl VectorStorage for SimpleMultiDenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> VectorRef {
+        self.get_vector_opt(key)
+            .expect("vector for point should be present")
+    }
+
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<VectorRef> {
+        self.vectors.get(key as usize).map(VectorRef::from)
+    }
 
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let multi_vector: &MultiDenseVector = vector.try_into()?;
+        let old_size = self
+            .vectors
+            .get(key as usize)
+            .map(|vector| vector.flattened_vectors().len())
+            .unwrap_or(0);
+        if key as usize >= self.vectors.len() {
+            self.vectors
+                .resize(key as usize + 1, MultiDenseVector::default());
+        }
+        self.vectors[key as usize] = multi_vector.clone();
+        self.total_sparse_size =
+            self.total_sparse_size - old_size + multi_vector.flattened_vectors().len();
+        self.set_deleted(key, false)?;
+        self.update_stored(key, false, Some(multi_vector))?;
         Ok(())
     }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            let other_deleted = other.is_deleted_vector(point_id);
+            let other_vector = other.get_vector(point_id);
+            let multi_vector: &MultiDenseVector = other_vector.try_into()?;
+
+            let new_id = self.vectors.len() as PointOffsetType;
+            self.total_sparse_size += multi_vector.flattened_vectors().len();
+            self.vectors.push(multi_vector.clone());
+            if other_deleted {
+                bitvec_set_deleted(&mut self.deleted, new_id, true);
+                self.deleted_count += 1;
+                self.deleted_db_wrapper.put(deleted_key(new_id), vec![1u8])?;
+            }
+            self.update_stored(new_id, other_deleted, Some(multi_vector))?;
+        }
+        Ok(start_index..self.vectors.len() as PointOffsetType)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        self.db_wrapper.flush()?;
+        self.deleted_db_wrapper.flush()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        // `key` was never a valid point (e.g. past the highest inserted id); there is no
+        // vector record to tombstone, so skip the write instead of persisting a phantom
+        // record that would make the load loop's `vectors.insert` panic on reopen.
+        if key as usize >= self.vectors.len() {
+            return Ok(false);
+        }
+        let was_deleted = self.set_deleted(key, true)?;
+        self.update_stored(key, true, None)?;
+        Ok(!was_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted
+            .get(key as usize)
+            .map(|flag| *flag)
+            .unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
 }
 
-// TODO integrate MultiDenseVector to Vectors enum to enable this implementation
-// impl VectorStorage for SimpleMultiDenseVectorStorage
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trips_raw_and_lz4() {
+        let record = StoredMultiDenseVector {
+            deleted: false,
+            vector: MultiDenseVector::default(),
+        };
+
+        for compressed in [false, true] {
+            let bytes = encode_record(&record, compressed).unwrap();
+            let expected_tag = if compressed {
+                RecordCodec::Lz4
+            } else {
+                RecordCodec::Raw
+            };
+            assert_eq!(bytes[0], expected_tag as u8);
+
+            let decoded = decode_record(&bytes).unwrap();
+            assert_eq!(decoded.deleted, record.deleted);
+            assert_eq!(
+                decoded.vector.flattened_vectors().len(),
+                record.vector.flattened_vectors().len()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_record_rejects_unknown_codec_tag() {
+        let bytes = vec![42u8, 0, 0, 0];
+        assert!(decode_record(&bytes).is_err());
+    }
+
+    fn open_test_storage(dir: &std::path::Path) -> Arc<AtomicRefCell<VectorStorageEnum>> {
+        let db = DB::open_default(dir).unwrap();
+        let database = Arc::new(RwLock::new(db));
+        open_simple_multi_dense_vector_storage(
+            database,
+            "test-multivector",
+            4,
+            Distance::Cosine,
+            &AtomicBool::new(false),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn metrics_track_reads_scoring_and_sparse_size() {
+        let dir = tempfile::Builder::new()
+            .prefix("simple-multi-dense")
+            .tempdir()
+            .unwrap();
+        let storage = open_test_storage(dir.path());
+        let mut guard = storage.borrow_mut();
+        let VectorStorageEnum::MultiDenseSimple(storage) = &mut *guard else {
+            panic!("expected simple multivector storage");
+        };
+
+        let vector = MultiDenseVector::default();
+        storage.insert_vector(0, VectorRef::from(&vector)).unwrap();
+        assert_eq!(storage.total_sparse_size, vector.flattened_vectors().len());
+
+        let _ = storage.get_multi(0);
+        storage.record_score_call();
+
+        let metrics = storage.metrics();
+        assert_eq!(metrics.get_multi_calls, 1);
+        assert_eq!(metrics.score_calls, 1);
+        assert_eq!(metrics.total_sparse_size, vector.flattened_vectors().len());
+    }
+
+    #[test]
+    fn vector_storage_insert_get_and_delete_round_trip() {
+        let dir = tempfile::Builder::new()
+            .prefix("simple-multi-dense")
+            .tempdir()
+            .unwrap();
+        let storage = open_test_storage(dir.path());
+        let mut guard = storage.borrow_mut();
+        let storage: &mut dyn VectorStorage = &mut *guard;
+
+        let vector = MultiDenseVector::default();
+        storage.insert_vector(0, VectorRef::from(&vector)).unwrap();
+        assert_eq!(storage.total_vector_count(), 1);
+        assert!(!storage.is_deleted_vector(0));
+        assert!(storage.get_vector_opt(0).is_some());
+
+        assert!(storage.delete_vector(0).unwrap());
+        assert!(storage.is_deleted_vector(0));
+        assert_eq!(storage.deleted_vector_count(), 1);
+    }
+
+    #[test]
+    fn delete_range_tombstones_only_valid_ids() {
+        let dir = tempfile::Builder::new()
+            .prefix("simple-multi-dense")
+            .tempdir()
+            .unwrap();
+        let storage = open_test_storage(dir.path());
+        let mut guard = storage.borrow_mut();
+        let VectorStorageEnum::MultiDenseSimple(storage) = &mut *guard else {
+            panic!("expected simple multivector storage");
+        };
+
+        let vector = MultiDenseVector::default();
+        for point_id in 0..5 {
+            storage
+                .insert_vector(point_id, VectorRef::from(&vector))
+                .unwrap();
+        }
+
+        // `to` reaches well past the valid id range; those ids were never inserted and
+        // must be skipped rather than tombstoned.
+        storage.delete_range(1, 1_000).unwrap();
+
+        assert!(!storage.is_deleted_vector(0));
+        for point_id in 1..5 {
+            assert!(storage.is_deleted_vector(point_id));
+        }
+        assert_eq!(storage.deleted_vector_count(), 4);
+    }
+}
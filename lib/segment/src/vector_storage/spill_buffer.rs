@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use common::types::PointOffsetType;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Default number of most-recently-inserted records kept resident before the oldest ones
+/// are spilled to disk. Large enough to absorb normal RocksDB iteration jitter without
+/// touching the temp file, small enough to actually bound peak RAM on huge collections.
+const DEFAULT_RING_CAPACITY: usize = 4096;
+
+struct SpillEntry {
+    offset: u64,
+    len: u32,
+}
+
+/// `swapvec`-style staging buffer used while reconstructing an in-memory vector collection
+/// from an unordered source (e.g. a RocksDB column iterator).
+///
+/// Records are kept in a small in-memory ring keyed by `point_id`; once the ring grows
+/// past `ring_capacity`, the lowest (most settled) id is compressed and appended to an
+/// on-disk temp file instead of staying resident. [`SpillBuffer::drain_in_order`] then
+/// merges the remaining ring with whatever was spilled and yields every record back in
+/// ascending `point_id` order, so the caller can place each one at its correct offset
+/// (`vectors.insert(point_id, value)`) without ever holding the whole collection in RAM.
+pub struct SpillBuffer<T> {
+    ring_capacity: usize,
+    resident: BTreeMap<PointOffsetType, T>,
+    spill_file: File,
+    spill_index: BTreeMap<PointOffsetType, SpillEntry>,
+    spill_cursor: u64,
+}
+
+impl<T: Serialize + DeserializeOwned> SpillBuffer<T> {
+    #[allow(unused)]
+    pub fn new() -> OperationResult<Self> {
+        Self::with_capacity(DEFAULT_RING_CAPACITY)
+    }
+
+    pub fn with_capacity(ring_capacity: usize) -> OperationResult<Self> {
+        let spill_file = tempfile::tempfile().map_err(|err| {
+            OperationError::service_error(&format!("failed to create spill buffer file: {err}"))
+        })?;
+        Ok(Self {
+            ring_capacity,
+            resident: BTreeMap::new(),
+            spill_file,
+            spill_index: BTreeMap::new(),
+            spill_cursor: 0,
+        })
+    }
+
+    /// Buffer `value` for `point_id`, overwriting any previous value for that id whether it
+    /// is still resident or was already spilled to disk (the stale spilled bytes are simply
+    /// abandoned, same as an mmap record overwrite). Ids may arrive out of order; only once
+    /// the ring overflows does the smallest resident id get flushed, which keeps the
+    /// eventual [`SpillBuffer::drain_in_order`] pass close to a sequential temp-file read.
+    pub fn insert(&mut self, point_id: PointOffsetType, value: T) -> OperationResult<()> {
+        // Drop any stale spilled entry for this id so it isn't also yielded by
+        // `drain_in_order` once `value` lands back in `resident` below.
+        self.spill_index.remove(&point_id);
+        self.resident.insert(point_id, value);
+        while self.resident.len() > self.ring_capacity {
+            let lowest_id = *self
+                .resident
+                .keys()
+                .next()
+                .expect("ring is non-empty, checked by loop condition");
+            let value = self
+                .resident
+                .remove(&lowest_id)
+                .expect("key was just observed in the ring");
+            self.spill(lowest_id, &value)?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self, point_id: PointOffsetType, value: &T) -> OperationResult<()> {
+        let raw = bincode::serialize(value)
+            .map_err(|_| OperationError::service_error("cannot serialize spilled record"))?;
+        let compressed = lz4_flex::compress_prepend_size(&raw);
+        self.spill_file.write_all(&compressed).map_err(|err| {
+            OperationError::service_error(&format!("failed to write spill buffer file: {err}"))
+        })?;
+        self.spill_index.insert(
+            point_id,
+            SpillEntry {
+                offset: self.spill_cursor,
+                len: compressed.len() as u32,
+            },
+        );
+        self.spill_cursor += compressed.len() as u64;
+        Ok(())
+    }
+
+    /// Consume the buffer, yielding every `(point_id, value)` pair in ascending `point_id`
+    /// order by merging the resident ring with whatever was spilled to disk.
+    ///
+    /// This is a real merge, not a collect-then-sort: both `resident` and `spill_index`
+    /// are already ordered by `point_id` (both are `BTreeMap`s), so [`DrainIter`] only ever
+    /// needs to hold the two next candidates plus one decoded record at a time, keeping
+    /// peak memory bounded regardless of how much was spilled.
+    pub fn drain_in_order(self) -> OperationResult<DrainIter<T>> {
+        Ok(DrainIter::new(
+            self.spill_file,
+            self.spill_index,
+            self.resident,
+        ))
+    }
+}
+
+/// Streaming merge of a [`SpillBuffer`]'s resident ring and its on-disk spill file, produced
+/// by [`SpillBuffer::drain_in_order`]. Each call to `next` decodes at most one spilled
+/// record, so draining never holds more than one record's worth of spilled data resident.
+pub struct DrainIter<T> {
+    spill_file: File,
+    spill_iter: std::collections::btree_map::IntoIter<PointOffsetType, SpillEntry>,
+    resident_iter: std::collections::btree_map::IntoIter<PointOffsetType, T>,
+    next_spill: Option<(PointOffsetType, SpillEntry)>,
+    next_resident: Option<(PointOffsetType, T)>,
+}
+
+impl<T: DeserializeOwned> DrainIter<T> {
+    fn new(
+        spill_file: File,
+        spill_index: BTreeMap<PointOffsetType, SpillEntry>,
+        resident: BTreeMap<PointOffsetType, T>,
+    ) -> Self {
+        let mut spill_iter = spill_index.into_iter();
+        let mut resident_iter = resident.into_iter();
+        let next_spill = spill_iter.next();
+        let next_resident = resident_iter.next();
+        Self {
+            spill_file,
+            spill_iter,
+            resident_iter,
+            next_spill,
+            next_resident,
+        }
+    }
+
+    fn read_spilled(&mut self, entry: SpillEntry) -> OperationResult<T> {
+        self.spill_file
+            .seek(SeekFrom::Start(entry.offset))
+            .map_err(|err| {
+                OperationError::service_error(&format!(
+                    "failed to seek spill buffer file: {err}"
+                ))
+            })?;
+        let mut compressed = vec![0u8; entry.len as usize];
+        self.spill_file.read_exact(&mut compressed).map_err(|err| {
+            OperationError::service_error(&format!("failed to read spill buffer file: {err}"))
+        })?;
+        let raw = lz4_flex::decompress_size_prepended(&compressed).map_err(|err| {
+            OperationError::service_error(&format!("lz4 decompress failed: {err}"))
+        })?;
+        bincode::deserialize(&raw)
+            .map_err(|_| OperationError::service_error("cannot deserialize spilled record"))
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for DrainIter<T> {
+    type Item = OperationResult<(PointOffsetType, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let take_spill = match (&self.next_spill, &self.next_resident) {
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some((spill_id, _)), Some((resident_id, _))) => spill_id <= resident_id,
+            (None, None) => return None,
+        };
+
+        if take_spill {
+            let (point_id, entry) = self.next_spill.take().expect("checked above");
+            self.next_spill = self.spill_iter.next();
+            Some(self.read_spilled(entry).map(|value| (point_id, value)))
+        } else {
+            let pair = self.next_resident.take().expect("checked above");
+            self.next_resident = self.resident_iter.next();
+            Some(Ok(pair))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_in_order_merges_spilled_and_resident_ascending() {
+        let mut buffer = SpillBuffer::<u32>::with_capacity(2).unwrap();
+        for point_id in 0..10u32 {
+            buffer
+                .insert(point_id as PointOffsetType, point_id * 10)
+                .unwrap();
+        }
+
+        let drained: Vec<(PointOffsetType, u32)> = buffer
+            .drain_in_order()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+
+        let expected: Vec<(PointOffsetType, u32)> = (0..10u32)
+            .map(|point_id| (point_id as PointOffsetType, point_id * 10))
+            .collect();
+        assert_eq!(drained, expected);
+    }
+
+    #[test]
+    fn insert_overwrites_value_for_same_point_id() {
+        let mut buffer = SpillBuffer::<u32>::with_capacity(4).unwrap();
+        buffer.insert(1, 100).unwrap();
+        buffer.insert(1, 200).unwrap();
+
+        let drained: Vec<(PointOffsetType, u32)> = buffer
+            .drain_in_order()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+        assert_eq!(drained, vec![(1, 200)]);
+    }
+
+    #[test]
+    fn insert_overwrites_an_already_spilled_point_id() {
+        let mut buffer = SpillBuffer::<u32>::with_capacity(2).unwrap();
+        // Push id 0 out to disk by filling the ring past capacity.
+        buffer.insert(0, 1).unwrap();
+        buffer.insert(1, 2).unwrap();
+        buffer.insert(2, 3).unwrap();
+
+        // Re-insert id 0 after it was already spilled; it must not be yielded twice.
+        buffer.insert(0, 100).unwrap();
+
+        let drained: Vec<(PointOffsetType, u32)> = buffer
+            .drain_in_order()
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect();
+        assert_eq!(drained, vec![(0, 100), (1, 2), (2, 3)]);
+    }
+}